@@ -1,7 +1,8 @@
 
-// importing all the necessary libraries 
-use std::collections::{HashMap, HashSet, VecDeque};
+// importing all the necessary libraries
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::collections::hash_map::Entry;
+use std::cmp::Reverse;
 use csv::ReaderBuilder;
 use std::fs::File;
 use std::io::Write;
@@ -9,68 +10,139 @@ use std::error::Error;
 use std::env;
 use std::path::Path;
 
+// OrderedCost wraps a f64 edge cost so it can sit inside a BinaryHeap, since f64 only
+// implements PartialOrd. add_weighted_edge does not reject NaN, so when partial_cmp
+// returns None (a NaN cost) the two costs are treated as equal instead of panicking.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct OrderedCost(f64);
+
+impl Eq for OrderedCost {}
+
+impl PartialOrd for OrderedCost {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrderedCost {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.partial_cmp(&other.0).unwrap_or(std::cmp::Ordering::Equal)
+    }
+}
+
 #[derive(Debug)]
 pub struct Graph {
-    adj: HashMap<String, HashSet<String>>,
+    adj: HashMap<String, HashMap<String, f64>>,
+    // optional (lat, lon) for nodes that represent real places, e.g. airports in a flight-path graph
+    coords: HashMap<String, (f64, f64)>,
+}
+
+// great-circle distance in kilometers between two (lat, lon) points, used as the A* heuristic
+fn haversine_km(a: (f64, f64), b: (f64, f64)) -> f64 {
+    const EARTH_RADIUS_KM: f64 = 6371.0;
+    let (lat1, lon1) = a;
+    let (lat2, lon2) = b;
+
+    let d_lat = (lat2 - lat1).to_radians();
+    let d_lon = (lon2 - lon1).to_radians();
+    let lat1 = lat1.to_radians();
+    let lat2 = lat2.to_radians();
+
+    let sin_half_lat = (d_lat / 2.0).sin();
+    let sin_half_lon = (d_lon / 2.0).sin();
+    let h = sin_half_lat * sin_half_lat + lat1.cos() * lat2.cos() * sin_half_lon * sin_half_lon;
+
+    EARTH_RADIUS_KM * 2.0 * h.sqrt().asin()
+}
+
+// the result of comparing two graphs built from different snapshots of the underlying CSVs:
+// everything that showed up, and everything that went away
+#[derive(Debug, PartialEq)]
+pub struct GraphDiff {
+    pub added_nodes: Vec<String>,
+    pub removed_nodes: Vec<String>,
+    pub added_edges: Vec<(String, String)>,
+    pub removed_edges: Vec<(String, String)>,
+}
+
+// normalizing an undirected pair so (a,b) and (b,a) are treated as the same edge
+fn normalize_pair(a: &str, b: &str) -> (String, String) {
+    if a <= b {
+        (a.to_string(), b.to_string())
+    } else {
+        (b.to_string(), a.to_string())
+    }
 }
 
 // the following defines a graph sturct with a single field 'adj'
-// this structure is used to represent a directed graph where the key is a node and the value is a set of nodes to which it is connected
+// this structure is used to represent a directed graph where the key is a node and the value is a map of neighbor -> edge weight
 
 impl Graph {
     // creating an empty graph
     pub fn new() -> Self {
-        Graph { adj: HashMap::new() }
+        Graph { adj: HashMap::new(), coords: HashMap::new() }
     }
 
-    // this part adds a node with a string label and if the node already exists it should do nothing 
+    // this part adds a node with a string label and if the node already exists it should do nothing
 
     pub fn add_node(&mut self, u: String) {
-        self.adj.entry(u).or_insert(HashSet::new());
+        self.adj.entry(u).or_insert(HashMap::new());
     }
 
-    // generating DOT representation of the graph, this is used for visualizing the graph 
+    // adding a node that also carries geographic coordinates, e.g. an airport's (lat, lon), so astar can use a haversine heuristic for it
+    pub fn add_geo_node(&mut self, label: String, lat: f64, lon: f64) {
+        self.add_node(label.clone());
+        self.coords.insert(label, (lat, lon));
+    }
+
+    // generating DOT representation of the graph, this is used for visualizing the graph
 
     pub fn to_dot(&self) -> String {
         let mut dot = String::from("digraph G {\n");
         for (node, edges) in &self.adj {
-            for edge in edges {
-                dot.push_str(&format!("    \"{}\" -> \"{}\";\n", node, edge));
+            for (edge, weight) in edges {
+                dot.push_str(&format!("    \"{}\" -> \"{}\" [label=\"{}\"];\n", node, edge, weight));
             }
         }
         dot.push_str("}\n");
         dot
     }
 
-    // the next part adds a directed edge from u to v (u->v)
-    fn add_directed_edge(&mut self, u: String, v: String) {
+    // the next part adds a directed edge from u to v (u->v) with a cost attached
+    fn add_weighted_directed_edge(&mut self, u: String, v: String, w: f64) {
         match self.adj.entry(u) {
             Entry::Occupied(succ) => {
-                succ.into_mut().insert(v);
+                succ.into_mut().insert(v, w);
             }
             Entry::Vacant(succ) => {
-                succ.insert(HashSet::from([v]));
+                succ.insert(HashMap::from([(v, w)]));
             }
         }
     }
 
-    // adding an edge in the graph (u<->v)
+    // adding an edge in the graph (u<->v) with unit weight, e.g. for plain degrees of separation
     pub fn add_edge(&mut self, u: String, v: String) {
-        self.add_directed_edge(u.clone(), v.clone());
-        self.add_directed_edge(v, u);
+        self.add_weighted_edge(u, v, 1.0);
+    }
+
+    // adding an edge in the graph (u<->v) carrying a cost, e.g. the distance between two airports
+    pub fn add_weighted_edge(&mut self, u: String, v: String, w: f64) {
+        self.add_weighted_directed_edge(u.clone(), v.clone(), w);
+        self.add_weighted_directed_edge(v, u, w);
     }
 
-    //the following returns a set of all nodes present in the graph 
+    //the following returns a set of all nodes present in the graph
     pub fn nodes(&self) -> HashSet<String> {
         self.adj.keys().cloned().collect()
     }
 
-    // this part returns the adjacent nodes of any given node 'u'
-    pub fn adj(&self, u: &String) -> Option<&HashSet<String>> {
+    // this part returns the adjacent nodes of any given node 'u', alongside the cost of each edge
+    pub fn adj(&self, u: &String) -> Option<&HashMap<String, f64>> {
         self.adj.get(u)
     }
 
-    // computing the degrees of separation between two nodes and they are considered the shortest path between two nodes in a graph 
+    // computing the degrees of separation between two nodes and they are considered the shortest path between two nodes in a graph
+    // this is just shortest_path with every edge treated as unit cost, counting hops instead of summing weights
     pub fn degrees_of_separation(&self, start: &String, end: &String) -> Option<usize> {
         if start == end {
             return Some(0);
@@ -81,11 +153,11 @@ impl Graph {
         let mut queue = VecDeque::new();
         queue.push_back((start.clone(), 0));
 
-        //we now begins a loop that continues as long as there are elements in the queue 
+        //we now begins a loop that continues as long as there are elements in the queue
         while let Some((current, distance)) = queue.pop_front() {
 
-            //if current == *end checks if the current node is the destination node, and if so returns the distence to this node 
-            if current == *end { 
+            //if current == *end checks if the current node is the destination node, and if so returns the distence to this node
+            if current == *end {
                 return Some(distance);
             }
 
@@ -94,9 +166,9 @@ impl Graph {
                 continue;
             }
 
-            //retrieves the set of nodes that are adjacent to the current node 
+            //retrieves the set of nodes that are adjacent to the current node
             if let Some(neighbors) = self.adj.get(&current) {
-                for neighbor in neighbors {
+                for neighbor in neighbors.keys() {
                     if !visited.contains(neighbor) {
                         queue.push_back((neighbor.clone(), distance + 1));
                     }
@@ -106,6 +178,63 @@ impl Graph {
         None
     }
 
+    // computing the minimum-cost path between two nodes using Dijkstra's algorithm, returning the total cost and the sequence of nodes visited
+    pub fn shortest_path(&self, start: &String, end: &String) -> Option<(f64, Vec<String>)> {
+        // dist holds the best known cost to reach each node so far
+        let mut dist: HashMap<String, f64> = HashMap::new();
+        // came_from lets us walk back from `end` to `start` once we're done
+        let mut came_from: HashMap<String, String> = HashMap::new();
+        let mut visited: HashSet<String> = HashSet::new();
+
+        let mut heap: BinaryHeap<Reverse<(OrderedCost, String)>> = BinaryHeap::new();
+        dist.insert(start.clone(), 0.0);
+        heap.push(Reverse((OrderedCost(0.0), start.clone())));
+
+        while let Some(Reverse((OrderedCost(cost), u))) = heap.pop() {
+            if u == *end {
+                return Some((cost, self.reconstruct_path(&came_from, start, end)));
+            }
+
+            // the cheapest way to reach u has already been finalized, so a stale heap entry can be skipped
+            if !visited.insert(u.clone()) {
+                continue;
+            }
+
+            if let Some(neighbors) = self.adj.get(&u) {
+                for (v, w) in neighbors {
+                    let next_cost = cost + w;
+                    let better = match dist.get(v) {
+                        Some(&best) => next_cost < best,
+                        None => true,
+                    };
+                    if better {
+                        dist.insert(v.clone(), next_cost);
+                        came_from.insert(v.clone(), u.clone());
+                        heap.push(Reverse((OrderedCost(next_cost), v.clone())));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    // walking the came_from map backwards from `end` to `start` to rebuild the full node sequence
+    fn reconstruct_path(&self, came_from: &HashMap<String, String>, start: &String, end: &String) -> Vec<String> {
+        let mut path = vec![end.clone()];
+        let mut current = end.clone();
+        while current != *start {
+            match came_from.get(&current) {
+                Some(prev) => {
+                    path.push(prev.clone());
+                    current = prev.clone();
+                }
+                None => break,
+            }
+        }
+        path.reverse();
+        path
+    }
+
     // the following is a method to add nodes and edges from CSV data
     pub fn add_from_csv(&mut self, filename: &str) -> Result<(), Box<dyn Error>> {
         let file = File::open(filename)?;
@@ -119,6 +248,390 @@ impl Graph {
 
         Ok(())
     }
+
+    // partitioning all nodes into the connected islands they belong to, e.g. grouping a bipartite subreddit-user graph into threads of mutually reachable nodes
+    pub fn connected_components(&self) -> Vec<HashSet<String>> {
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut components = Vec::new();
+
+        for node in self.adj.keys() {
+            if seen.contains(node) {
+                continue;
+            }
+            let component = self.bfs_component(node);
+            seen.extend(component.iter().cloned());
+            components.push(component);
+        }
+
+        components
+    }
+
+    // the island containing a single node, or None if the node isn't in the graph
+    pub fn component_of(&self, node: &String) -> Option<HashSet<String>> {
+        if !self.adj.contains_key(node) {
+            return None;
+        }
+        Some(self.bfs_component(node))
+    }
+
+    // BFS out from `start`, collecting every node reachable from it into one set
+    fn bfs_component(&self, start: &String) -> HashSet<String> {
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+        visited.insert(start.clone());
+        queue.push_back(start.clone());
+
+        while let Some(current) = queue.pop_front() {
+            if let Some(neighbors) = self.adj.get(&current) {
+                for neighbor in neighbors.keys() {
+                    if visited.insert(neighbor.clone()) {
+                        queue.push_back(neighbor.clone());
+                    }
+                }
+            }
+        }
+
+        visited
+    }
+
+    // the set of normalized undirected edges present in this graph, used by diff and to_dot_diff
+    fn edge_set(&self) -> HashSet<(String, String)> {
+        let mut edges = HashSet::new();
+        for (node, neighbors) in &self.adj {
+            for neighbor in neighbors.keys() {
+                edges.insert(normalize_pair(node, neighbor));
+            }
+        }
+        edges
+    }
+
+    // comparing this graph against a rebuilt one, reporting which nodes and edges were added or removed
+    pub fn diff(&self, other: &Graph) -> GraphDiff {
+        let self_nodes = self.nodes();
+        let other_nodes = other.nodes();
+
+        let mut added_nodes: Vec<String> = other_nodes.difference(&self_nodes).cloned().collect();
+        let mut removed_nodes: Vec<String> = self_nodes.difference(&other_nodes).cloned().collect();
+        added_nodes.sort();
+        removed_nodes.sort();
+
+        let self_edges = self.edge_set();
+        let other_edges = other.edge_set();
+
+        let mut added_edges: Vec<(String, String)> = other_edges.difference(&self_edges).cloned().collect();
+        let mut removed_edges: Vec<(String, String)> = self_edges.difference(&other_edges).cloned().collect();
+        added_edges.sort();
+        removed_edges.sort();
+
+        GraphDiff { added_nodes, removed_nodes, added_edges, removed_edges }
+    }
+
+    // like to_dot, but colors edges that `other` added in green and edges it removed in red, so the change is directly visualizable
+    pub fn to_dot_diff(&self, other: &Graph) -> String {
+        let diff = self.diff(other);
+        let added: HashSet<(String, String)> = diff.added_edges.into_iter().collect();
+        let removed: HashSet<(String, String)> = diff.removed_edges.into_iter().collect();
+
+        let mut dot = String::from("digraph G {\n");
+        let mut drawn: HashSet<(String, String)> = HashSet::new();
+
+        for (node, neighbors) in &other.adj {
+            for neighbor in neighbors.keys() {
+                let pair = normalize_pair(node, neighbor);
+                if !drawn.insert(pair.clone()) {
+                    continue;
+                }
+                let color = if added.contains(&pair) {
+                    "green"
+                } else {
+                    "black"
+                };
+                dot.push_str(&format!("    \"{}\" -> \"{}\" [color=\"{}\"];\n", node, neighbor, color));
+            }
+        }
+
+        for (a, b) in &removed {
+            dot.push_str(&format!("    \"{}\" -> \"{}\" [color=\"red\"];\n", a, b));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    // enumerating every simple route from `start` to `end`, with no cutoff on how long a path can get
+    pub fn all_paths(&self, start: &String, end: &String) -> Vec<Vec<String>> {
+        self.all_paths_limited(start, end, None)
+    }
+
+    // same as all_paths, but stops extending a path once it reaches `max_len` nodes
+    pub fn all_paths_limited(&self, start: &String, end: &String, max_len: Option<usize>) -> Vec<Vec<String>> {
+        let mut results = Vec::new();
+        let mut path = vec![start.clone()];
+        let mut on_path: HashSet<String> = HashSet::from([start.clone()]);
+        self.dfs_all_paths(start, end, &mut path, &mut on_path, max_len, &mut results);
+        results
+    }
+
+    // DFS carrying the current path; neighbors already on the path are skipped so every result is a simple path
+    fn dfs_all_paths(
+        &self,
+        current: &String,
+        end: &String,
+        path: &mut Vec<String>,
+        on_path: &mut HashSet<String>,
+        max_len: Option<usize>,
+        results: &mut Vec<Vec<String>>,
+    ) {
+        if current == end {
+            results.push(path.clone());
+            return;
+        }
+        if let Some(max) = max_len {
+            if path.len() >= max {
+                return;
+            }
+        }
+
+        if let Some(neighbors) = self.adj.get(current) {
+            for neighbor in neighbors.keys() {
+                if on_path.contains(neighbor) {
+                    continue;
+                }
+                path.push(neighbor.clone());
+                on_path.insert(neighbor.clone());
+                self.dfs_all_paths(neighbor, end, path, on_path, max_len, results);
+                path.pop();
+                on_path.remove(neighbor);
+            }
+        }
+    }
+
+    // like all_paths, but exactly one node along the way is allowed to appear twice, for exploring near-alternative routes
+    pub fn all_paths_allow_one_revisit(&self, start: &String, end: &String, max_len: Option<usize>) -> Vec<Vec<String>> {
+        let mut results = Vec::new();
+        let mut path = vec![start.clone()];
+        let mut visit_count: HashMap<String, usize> = HashMap::from([(start.clone(), 1)]);
+        self.dfs_all_paths_revisit(start, end, &mut path, &mut visit_count, false, max_len, &mut results);
+        results
+    }
+
+    // same DFS as dfs_all_paths, but a node may be stepped onto a second time as long as no other
+    // node has already used up the single allowed double visit (tracked by `used_double_visit`)
+    fn dfs_all_paths_revisit(
+        &self,
+        current: &String,
+        end: &String,
+        path: &mut Vec<String>,
+        visit_count: &mut HashMap<String, usize>,
+        used_double_visit: bool,
+        max_len: Option<usize>,
+        results: &mut Vec<Vec<String>>,
+    ) {
+        if current == end {
+            results.push(path.clone());
+            return;
+        }
+        if let Some(max) = max_len {
+            if path.len() >= max {
+                return;
+            }
+        }
+
+        if let Some(neighbors) = self.adj.get(current) {
+            for neighbor in neighbors.keys() {
+                let visits_so_far = *visit_count.get(neighbor).unwrap_or(&0);
+                let would_double_visit = visits_so_far == 1;
+                if visits_so_far >= 2 || (would_double_visit && used_double_visit) {
+                    continue;
+                }
+
+                path.push(neighbor.clone());
+                *visit_count.entry(neighbor.clone()).or_insert(0) += 1;
+                self.dfs_all_paths_revisit(
+                    neighbor,
+                    end,
+                    path,
+                    visit_count,
+                    used_double_visit || would_double_visit,
+                    max_len,
+                    results,
+                );
+                *visit_count.get_mut(neighbor).unwrap() -= 1;
+                path.pop();
+            }
+        }
+    }
+
+    // building the reverse adjacency (who points at each node) so we can BFS backwards from a target
+    fn reverse_adj(&self) -> HashMap<String, HashSet<String>> {
+        let mut rev: HashMap<String, HashSet<String>> = HashMap::new();
+        for node in self.adj.keys() {
+            rev.entry(node.clone()).or_insert(HashSet::new());
+        }
+        for (u, neighbors) in &self.adj {
+            for v in neighbors.keys() {
+                rev.entry(v.clone()).or_insert(HashSet::new()).insert(u.clone());
+            }
+        }
+        rev
+    }
+
+    // BFS on the reverse adjacency, collecting every node that can reach `dst`
+    fn backward_reachable(&self, dst: &String) -> HashSet<String> {
+        let rev = self.reverse_adj();
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+        visited.insert(dst.clone());
+        queue.push_back(dst.clone());
+
+        while let Some(current) = queue.pop_front() {
+            if let Some(preds) = rev.get(&current) {
+                for pred in preds {
+                    if visited.insert(pred.clone()) {
+                        queue.push_back(pred.clone());
+                    }
+                }
+            }
+        }
+
+        visited
+    }
+
+    // the induced subgraph of nodes that lie "between" src and dst: reachable from src, and able to reach dst
+    pub fn subgraph_between(&self, src: &String, dst: &String) -> Graph {
+        let forward = self.bfs_component(src);
+        let backward = self.backward_reachable(dst);
+        let keep: HashSet<String> = forward.intersection(&backward).cloned().collect();
+
+        let mut sub = Graph::new();
+        for node in &keep {
+            sub.add_node(node.clone());
+        }
+        for node in &keep {
+            if let Some(neighbors) = self.adj.get(node) {
+                for (neighbor, weight) in neighbors {
+                    if keep.contains(neighbor) {
+                        sub.add_weighted_directed_edge(node.clone(), neighbor.clone(), *weight);
+                    }
+                }
+            }
+        }
+        sub
+    }
+
+    // the haversine distance from `node` to `end`, or 0.0 (i.e. plain Dijkstra) if either is missing coordinates
+    fn heuristic(&self, node: &String, end: &String) -> f64 {
+        match (self.coords.get(node), self.coords.get(end)) {
+            (Some(&a), Some(&b)) => haversine_km(a, b),
+            _ => 0.0,
+        }
+    }
+
+    // minimum-cost path using A*: same priority-queue machinery as shortest_path, but the frontier is
+    // ordered by g + h instead of just g, using the haversine distance to `end` as the heuristic h
+    pub fn astar(&self, start: &String, end: &String) -> Option<(f64, Vec<String>)> {
+        let mut g_score: HashMap<String, f64> = HashMap::new();
+        let mut came_from: HashMap<String, String> = HashMap::new();
+        let mut visited: HashSet<String> = HashSet::new();
+
+        let mut open: BinaryHeap<Reverse<(OrderedCost, String)>> = BinaryHeap::new();
+        g_score.insert(start.clone(), 0.0);
+        open.push(Reverse((OrderedCost(self.heuristic(start, end)), start.clone())));
+
+        while let Some(Reverse((_, u))) = open.pop() {
+            if u == *end {
+                let cost = *g_score.get(&u).unwrap();
+                return Some((cost, self.reconstruct_path(&came_from, start, end)));
+            }
+
+            if !visited.insert(u.clone()) {
+                continue;
+            }
+
+            let g_u = *g_score.get(&u).unwrap();
+            if let Some(neighbors) = self.adj.get(&u) {
+                for (v, w) in neighbors {
+                    let tentative_g = g_u + w;
+                    let better = match g_score.get(v) {
+                        Some(&best) => tentative_g < best,
+                        None => true,
+                    };
+                    if better {
+                        g_score.insert(v.clone(), tentative_g);
+                        came_from.insert(v.clone(), u.clone());
+                        let f = tentative_g + self.heuristic(v, end);
+                        open.push(Reverse((OrderedCost(f), v.clone())));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    // the cheapest ordering in which to visit every stop, starting from stops[0]; brute-forces the
+    // permutations of the remaining stops, so this is only meant for small stop lists (~10 or fewer)
+    pub fn best_tour(&self, stops: &[String]) -> Option<(f64, Vec<String>)> {
+        if stops.is_empty() {
+            return None;
+        }
+        if stops.len() == 1 {
+            return Some((0.0, vec![stops[0].clone()]));
+        }
+
+        // all-pairs shortest costs among the stops, computed once up front by running Dijkstra for each ordered pair of stops
+        let mut pair_paths: HashMap<(String, String), (f64, Vec<String>)> = HashMap::new();
+        for a in stops {
+            for b in stops {
+                if a == b {
+                    continue;
+                }
+                if let Some((cost, path)) = self.shortest_path(a, b) {
+                    pair_paths.insert((a.clone(), b.clone()), (cost, path));
+                }
+            }
+        }
+
+        let origin = stops[0].clone();
+        let mut rest: Vec<String> = stops[1..].to_vec();
+        let mut best: Option<(f64, Vec<String>)> = None;
+
+        permutations(&mut rest, 0, &mut |ordering| {
+            let mut total_cost = 0.0;
+            let mut full_path: Vec<String> = vec![origin.clone()];
+            let mut current = origin.clone();
+
+            for next in ordering {
+                match pair_paths.get(&(current.clone(), next.clone())) {
+                    Some((cost, path)) => {
+                        total_cost += cost;
+                        full_path.extend(path.iter().skip(1).cloned());
+                        current = next.clone();
+                    }
+                    None => return, // this ordering hits a pair with no path between them, so it's not viable
+                }
+            }
+
+            if best.as_ref().map_or(true, |(best_cost, _)| total_cost < *best_cost) {
+                best = Some((total_cost, full_path));
+            }
+        });
+
+        best
+    }
+}
+
+// in-place swap-and-backtrack permutation generator: fixes items[0..k], then swaps each remaining
+// item into position k, recurses, and swaps it back, invoking `callback` once per full permutation
+fn permutations<F: FnMut(&[String])>(items: &mut Vec<String>, k: usize, callback: &mut F) {
+    if k == items.len() {
+        callback(items);
+        return;
+    }
+    for i in k..items.len() {
+        items.swap(k, i);
+        permutations(items, k + 1, callback);
+        items.swap(k, i);
+    }
 }
 
 fn main() -> Result<(), Box<dyn Error>> {